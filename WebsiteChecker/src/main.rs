@@ -4,10 +4,12 @@ use std::time::{Duration, Instant};
 use std::env;
 use std::fs::File;
 use std::io::{BufReader, BufRead};
-use std::collections::VecDeque;
-use std::net::TcpListener;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::net::{TcpListener, TcpStream};
 use std::io::Write;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 //struct to store the status of the website
 #[derive(Debug)]
@@ -17,10 +19,384 @@ struct WebsiteStatus {
     response_time: Duration,
     timestamp: DateTime<Utc>,
     headers_valid: bool,
+    //None = no cache entry existed yet, Some(true) = content differs from last run,
+    //Some(false) = server answered 304 Not Modified
+    changed: Option<bool>,
+    //how long to wait before re-checking this URL, derived from Cache-Control
+    poll_interval: Duration,
+    //the (url, status) of every redirect hop followed, in order
+    redirect_chain: Vec<(String, u16)>,
+    //the URL the chain actually landed on, after following any redirects
+    final_url: String,
+    //last few lines of content added since the previous poll, when tail mode is on
+    tail: Option<String>,
+    //how many bytes were newly read this poll, when tail mode is on
+    bytes_added: Option<u64>,
+    //whether an Authorization header was attached, from a matching entry in auth.json
+    auth_applied: bool,
+}
+
+//controls how many redirect hops website_checker will follow before giving up
+#[derive(Debug, Clone, Copy)]
+struct RedirectPolicy {
+    max_hops: usize,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        RedirectPolicy { max_hops: 10 }
+    }
+}
+
+//resolves a Location header against the URL that produced it, handling both
+//absolute URLs and paths relative to the current URL's origin or directory
+fn resolve_location(base: &str, location: &str) -> String {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return location.to_string();
+    }
+
+    let Some(scheme_end) = base.find("://") else {
+        return location.to_string();
+    };
+    let authority_start = scheme_end + 3;
+    let authority_end = base[authority_start..]
+        .find('/')
+        .map(|i| authority_start + i)
+        .unwrap_or(base.len());
+    let origin = &base[..authority_end];
+
+    if location.starts_with('/') {
+        format!("{}{}", origin, location)
+    } else {
+        let base_dir_end = base[authority_end..]
+            .rfind('/')
+            .map(|i| authority_end + i + 1)
+            .unwrap_or(authority_end);
+        format!("{}{}", &base[..base_dir_end], location)
+    }
+}
+
+//what we remember about a URL between runs so we can send conditional requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    last_status: u16,
+}
+
+type Cache = HashMap<String, CacheEntry>;
+
+//loads the cache file from the previous run, if any
+fn load_cache(path: &str) -> Cache {
+    match File::open(path) {
+        Ok(file) => serde_json::from_reader(BufReader::new(file)).unwrap_or_else(|e| {
+            eprintln!("Warning: failed to parse {}: {}", path, e);
+            Cache::new()
+        }),
+        Err(_) => Cache::new(),
+    }
+}
+
+//persists the cache so the next run can send conditional requests
+fn save_cache(path: &str, cache: &Cache) {
+    match File::create(path) {
+        Ok(file) => {
+            if let Err(e) = serde_json::to_writer_pretty(file, cache) {
+                eprintln!("Warning: failed to write {}: {}", path, e);
+            }
+        }
+        Err(e) => eprintln!("Warning: failed to create {}: {}", path, e),
+    }
+}
+
+//how many bytes of each URL we've already read, for tail mode's Range requests
+type TailOffsets = HashMap<String, u64>;
+
+//loads the saved tail offsets from the previous run, if any
+fn load_offsets(path: &str) -> TailOffsets {
+    match File::open(path) {
+        Ok(file) => serde_json::from_reader(BufReader::new(file)).unwrap_or_else(|e| {
+            eprintln!("Warning: failed to parse {}: {}", path, e);
+            TailOffsets::new()
+        }),
+        Err(_) => TailOffsets::new(),
+    }
+}
+
+//persists the tail offsets so the next run keeps reading where this one left off
+fn save_offsets(path: &str, offsets: &TailOffsets) {
+    match File::create(path) {
+        Ok(file) => {
+            if let Err(e) = serde_json::to_writer_pretty(file, offsets) {
+                eprintln!("Warning: failed to write {}: {}", path, e);
+            }
+        }
+        Err(e) => eprintln!("Warning: failed to create {}: {}", path, e),
+    }
+}
+
+//parses a "Content-Range: bytes <start>-<end>/<total>" header, returning (end, total)
+fn parse_content_range(value: &str) -> Option<(u64, u64)> {
+    let rest = value.strip_prefix("bytes ")?;
+    let (range, total) = rest.split_once('/')?;
+    let (_, end) = range.split_once('-')?;
+    let end: u64 = end.trim().parse().ok()?;
+    let total: u64 = total.trim().parse().ok()?;
+    Some((end, total))
+}
+
+//keeps only the last `n` lines of text, so a tail doesn't grow without bound
+fn last_lines(text: &str, n: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(n);
+    lines[start..].join("\n")
+}
+
+//how many trailing lines of newly-appended content to keep in WebsiteStatus.tail
+const TAIL_LINES: usize = 10;
+
+//credentials for one host pattern, loaded from auth.json
+#[derive(Debug, Clone, Deserialize)]
+struct AuthEntry {
+    scheme: String, // "Bearer" or "Basic"
+    token: String,
+}
+
+//maps a host pattern ("example.com" or "*.example.com") to the credentials to send it
+type AuthConfig = HashMap<String, AuthEntry>;
+
+//loads the per-host auth config, if any; missing/invalid file just means no auth is sent
+fn load_auth_config(path: &str) -> AuthConfig {
+    match File::open(path) {
+        Ok(file) => serde_json::from_reader(BufReader::new(file)).unwrap_or_else(|e| {
+            eprintln!("Warning: failed to parse {}: {}", path, e);
+            AuthConfig::new()
+        }),
+        Err(_) => AuthConfig::new(),
+    }
+}
+
+//extracts the host[:port] authority from a URL, e.g. "http://example.com:8080/a" -> "example.com:8080"
+fn host_of(url: &str) -> Option<&str> {
+    let scheme_end = url.find("://")?;
+    let authority_start = scheme_end + 3;
+    let authority_end = url[authority_start..]
+        .find('/')
+        .map(|i| authority_start + i)
+        .unwrap_or(url.len());
+    Some(&url[authority_start..authority_end])
+}
+
+//"*.example.com" matches any subdomain of example.com, anything else must match exactly
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host.to_lowercase().ends_with(&format!(".{}", suffix.to_lowercase())),
+        None => pattern.eq_ignore_ascii_case(host),
+    }
+}
+
+//finds the credentials configured for this URL's host, if any
+fn find_auth<'a>(auth_config: &'a AuthConfig, url: &str) -> Option<&'a AuthEntry> {
+    let host = host_of(url)?;
+    auth_config
+        .iter()
+        .find(|(pattern, _)| host_matches(pattern, host))
+        .map(|(_, entry)| entry)
+}
+
+//a stripped down HTTP response so the core logic doesn't depend on ureq directly
+#[derive(Debug, Clone)]
+pub struct HttpResp {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl HttpResp {
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    //header lookup is case-insensitive like real HTTP headers
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+//abstracts the request/response cycle so website_checker can be tested without real sockets
+pub trait HttpClient {
+    fn get(&self, url: &str, timeout: Duration, headers: &[(String, String)]) -> Result<HttpResp, String>;
+}
+
+//how long to allow for each phase of a request; connect/read are baked into the agent at
+//construction time, total is enforced per-request on top of them
+#[derive(Debug, Clone, Copy)]
+struct TimeoutConfig {
+    connect: Duration,
+    read: Duration,
+    total: Duration,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        TimeoutConfig {
+            connect: Duration::from_secs(5),
+            read: Duration::from_secs(10),
+            total: Duration::from_secs(15),
+        }
+    }
+}
+
+//best-effort guess at which timeout phase tripped, based on ureq's error message, so
+//WebsiteStatus.status can say more than just "timed out"
+fn timeout_phase(message: &str) -> Option<&'static str> {
+    let lower = message.to_lowercase();
+    if !lower.contains("timed out") && !lower.contains("timeout") {
+        return None;
+    }
+    if lower.contains("connect") {
+        Some("connect")
+    } else if lower.contains("read") {
+        Some("read")
+    } else {
+        Some("total")
+    }
+}
+
+//production client, backed by a real ureq agent. the agent is cheap to clone and pools
+//keep-alive connections, so a single instance is shared across every worker thread
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub fn new(timeouts: TimeoutConfig) -> Self {
+        UreqClient {
+            //redirects are followed manually in website_checker so the chain can be recorded
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(timeouts.connect)
+                .timeout_read(timeouts.read)
+                .redirects(0)
+                .build(),
+        }
+    }
+}
+
+//converts a raw ureq response into our HttpResp, draining the body in the process. shared by
+//the success path and the Error::Status path below, since ureq represents a non-2xx response
+//as an error that still carries a real response
+fn to_http_resp(response: ureq::Response) -> HttpResp {
+    let status = response.status();
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| response.header(&name).map(|v| (name.clone(), v.to_string())))
+        .collect();
+    let body = response.into_string().unwrap_or_default();
+    HttpResp {
+        status,
+        headers,
+        body,
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, timeout: Duration, headers: &[(String, String)]) -> Result<HttpResp, String> {
+        let mut request = self.agent.get(url).timeout(timeout);
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        match request.call() {
+            Ok(response) => Ok(to_http_resp(response)),
+            //ureq treats any status >= 400 as an error, but it's still a real response we need
+            //to hand back to website_checker (304/416/401/403/etc all flow through here)
+            Err(ureq::Error::Status(_, response)) => Ok(to_http_resp(response)),
+            Err(e) => {
+                let message = e.to_string();
+                match timeout_phase(&message) {
+                    Some(phase) => Err(format!("{} timeout: {}", phase, message)),
+                    None => Err(message),
+                }
+            }
+        }
+    }
+}
+
+//test-only client that maps URLs to canned responses, so tests don't need a real socket.
+//also records the headers each call was made with, so tests can assert on header-driven
+//behavior (conditional GET, Range, Authorization, User-Agent) instead of only inferring it
+//from response-status side effects
+pub struct MockClient {
+    responses: HashMap<String, Result<HttpResp, String>>,
+    received_headers: Mutex<HashMap<String, Vec<(String, String)>>>,
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        MockClient {
+            responses: HashMap::new(),
+            received_headers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_response(mut self, url: &str, resp: HttpResp) -> Self {
+        self.responses.insert(url.to_string(), Ok(resp));
+        self
+    }
+
+    pub fn with_error(mut self, url: &str, err: &str) -> Self {
+        self.responses.insert(url.to_string(), Err(err.to_string()));
+        self
+    }
+
+    //returns the headers sent on the most recent `get` call for `url`, if any
+    pub fn headers_sent_to(&self, url: &str) -> Option<Vec<(String, String)>> {
+        self.received_headers.lock().unwrap().get(url).cloned()
+    }
+}
+
+impl HttpClient for MockClient {
+    fn get(&self, url: &str, _timeout: Duration, headers: &[(String, String)]) -> Result<HttpResp, String> {
+        self.received_headers
+            .lock()
+            .unwrap()
+            .insert(url.to_string(), headers.to_vec());
+
+        self.responses
+            .get(url)
+            .cloned()
+            .unwrap_or_else(|| Err(format!("no mock response registered for {}", url)))
+    }
+}
+
+//parses the Cache-Control response header into a re-poll interval, falling back to
+//default_interval when no directive is present. no-store/no-cache means "always re-fetch".
+fn parse_poll_interval(cache_control: Option<&str>, default_interval: Duration) -> Duration {
+    let Some(value) = cache_control else {
+        return default_interval;
+    };
+
+    let directives: Vec<&str> = value.split(',').map(|d| d.trim()).collect();
+    if directives
+        .iter()
+        .any(|d| d.eq_ignore_ascii_case("no-store") || d.eq_ignore_ascii_case("no-cache"))
+    {
+        return Duration::from_secs(0);
+    }
+
+    directives
+        .iter()
+        .find_map(|d| d.strip_prefix("max-age="))
+        .and_then(|n| n.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(default_interval)
 }
 
 //validate the headers of the HTTP response
-fn validate_headers(response: &ureq::Response) -> bool {
+fn validate_headers(response: &HttpResp) -> bool {
     //Could not get it to fully work
     //Only checks if the content-type contains "application/json"
     response
@@ -30,29 +406,170 @@ fn validate_headers(response: &ureq::Response) -> bool {
 }
 
 //Chech the status of a website/GET requests
-pub fn website_checker(url: String, timeout: Duration) -> WebsiteStatus {
+pub fn website_checker(
+    client: &(dyn HttpClient + Send + Sync),
+    url: String,
+    timeout: Duration,
+    cache: &Arc<Mutex<Cache>>,
+    default_interval: Duration,
+    redirect_policy: RedirectPolicy,
+    tail_mode: bool,
+    tail_offsets: &Arc<Mutex<TailOffsets>>,
+    user_agent: &str,
+    auth_config: &AuthConfig,
+) -> WebsiteStatus {
     //initalizing the timing
     let start = Instant::now();
     let timestamp = Utc::now();
 
-    //create an agent to make the requests
-    let agent = ureq::agent();
-
     //default Errors for bad results and invalid headers
-    let mut response_result = Err("Request error".to_string());
     let mut headers_valid = false;
+    let mut changed = None;
+    let mut poll_interval = default_interval;
+    let mut redirect_chain: Vec<(String, u16)> = Vec::new();
+    let mut tail = None;
+    let mut bytes_added = None;
+    let mut auth_applied = false;
 
-    //Attempting the GET request
-    match agent.get(&url).timeout(timeout).call() {
-        Ok(response) => {
-            //Stores the status code and checks if the header is valid
-            response_result = Ok(response.status());
-            headers_valid = validate_headers(&response);
+    //grab the previous cache entry (if any) and build the conditional request headers from it;
+    //the cache is keyed on the original URL, but the hop that actually owns the cached
+    //representation is whichever URL the redirect chain resolves to, so these are sent on
+    //every hop below rather than just the first
+    let prior_entry = cache.lock().unwrap().get(&url).cloned();
+    let mut request_headers = Vec::new();
+    if let Some(entry) = &prior_entry {
+        if let Some(etag) = &entry.etag {
+            request_headers.push(("If-None-Match".to_string(), etag.clone()));
         }
-        Err(e) => {
-            response_result = Err(format!("Request failed: {} for URL {}", e, url));
+        if let Some(last_modified) = &entry.last_modified {
+            request_headers.push(("If-Modified-Since".to_string(), last_modified.clone()));
         }
     }
+    if tail_mode {
+        //ask the server for only what we haven't read yet
+        let prior_offset = tail_offsets.lock().unwrap().get(&url).copied().unwrap_or(0);
+        request_headers.push(("Range".to_string(), format!("bytes={}-", prior_offset)));
+    }
+
+    //follows the redirect chain by hand (ureq's auto-follow is disabled), so we can record
+    //every hop and detect loops
+    let mut current_url = url.clone();
+    let mut seen = HashSet::new();
+    let (response_result, final_url) = loop {
+        if !seen.insert(current_url.clone()) {
+            break (Err(format!("Redirect loop detected at {}", current_url)), current_url);
+        }
+
+        //conditional/Range headers are forwarded to every hop: a redirector that doesn't
+        //understand them just ignores them and redirects again, but the final resource in
+        //the chain needs them to actually serve a 304/206. User-Agent and auth are likewise
+        //attached at every hop since they're per-request/per-host, not per-URL
+        let mut headers = request_headers.clone();
+        headers.push(("User-Agent".to_string(), user_agent.to_string()));
+        if let Some(entry) = find_auth(auth_config, &current_url) {
+            headers.push(("Authorization".to_string(), format!("{} {}", entry.scheme, entry.token)));
+            auth_applied = true;
+        }
+
+        match client.get(&current_url, timeout, &headers) {
+            //304 is technically in the 3xx range but it means "unchanged", not "redirect" -
+            //it's handled by the dedicated arm below and must not be caught here
+            Ok(response) if (300..400).contains(&response.status()) && response.status() != 304 => {
+                let status = response.status();
+                redirect_chain.push((current_url.clone(), status));
+
+                if redirect_chain.len() > redirect_policy.max_hops {
+                    break (Err(format!("Too many redirects for URL {}", url)), current_url);
+                }
+
+                match response.header("Location") {
+                    Some(location) => current_url = resolve_location(&current_url, location),
+                    None => break (Err(format!("Redirect with no Location header for URL {}", current_url)), current_url),
+                }
+            }
+            Ok(response) if response.status() == 304 => {
+                //server says nothing changed, reuse the status we saw last time
+                changed = Some(false);
+                let last_status = prior_entry.as_ref().map(|e| e.last_status).unwrap_or(304);
+                headers_valid = validate_headers(&response);
+                poll_interval = parse_poll_interval(response.header("Cache-Control"), default_interval);
+                break (Ok(last_status), current_url);
+            }
+            Ok(response) if tail_mode && response.status() == 416 => {
+                //range not satisfiable - nothing has been appended since last poll
+                headers_valid = validate_headers(&response);
+                changed = Some(false);
+                poll_interval = parse_poll_interval(response.header("Cache-Control"), default_interval);
+                bytes_added = Some(0);
+                break (Ok(response.status()), current_url);
+            }
+            Ok(response) if tail_mode && response.status() == 206 => {
+                //server honored the Range request; advance the offset by what came back
+                headers_valid = validate_headers(&response);
+                changed = Some(true);
+                poll_interval = parse_poll_interval(response.header("Cache-Control"), default_interval);
+
+                let prior_offset = tail_offsets.lock().unwrap().get(&url).copied().unwrap_or(0);
+                match response.header("Content-Range").and_then(parse_content_range) {
+                    Some((_, total)) if total < prior_offset => {
+                        //resource shrank/rotated, start over from the beginning next poll
+                        tail_offsets.lock().unwrap().insert(url.clone(), 0);
+                        bytes_added = Some(0);
+                    }
+                    Some((end, _)) => {
+                        tail_offsets.lock().unwrap().insert(url.clone(), end + 1);
+                        tail = Some(last_lines(&response.body, TAIL_LINES));
+                        bytes_added = Some(response.body.len() as u64);
+                    }
+                    None => {
+                        tail_offsets.lock().unwrap().insert(url.clone(), prior_offset + response.body.len() as u64);
+                        tail = Some(last_lines(&response.body, TAIL_LINES));
+                        bytes_added = Some(response.body.len() as u64);
+                    }
+                }
+
+                let entry = CacheEntry {
+                    etag: response.header("ETag").map(|v| v.to_string()),
+                    last_modified: response.header("Last-Modified").map(|v| v.to_string()),
+                    last_status: response.status(),
+                };
+                cache.lock().unwrap().insert(url.clone(), entry);
+
+                break (Ok(response.status()), current_url);
+            }
+            Ok(response) => {
+                //Stores the status code and checks if the header is valid
+                headers_valid = validate_headers(&response);
+                //None when there was no prior cache entry to compare against, Some(true) when
+                //a fresh 200 follows one (304 above is the only path that reports Some(false))
+                changed = prior_entry.is_some().then_some(true);
+                poll_interval = parse_poll_interval(response.header("Cache-Control"), default_interval);
+
+                //only a successful response actually reflects the current state of the
+                //resource - a transient 4xx/5xx here shouldn't clobber the tracked tail
+                //offset or overwrite the cache with the error page's ETag/Last-Modified,
+                //same as the 304/416 arms above already leave them untouched
+                if (200..300).contains(&response.status()) {
+                    if tail_mode {
+                        //server ignored the Range request and sent the whole body back
+                        tail = Some(last_lines(&response.body, TAIL_LINES));
+                        bytes_added = Some(response.body.len() as u64);
+                        tail_offsets.lock().unwrap().insert(url.clone(), response.body.len() as u64);
+                    }
+
+                    let entry = CacheEntry {
+                        etag: response.header("ETag").map(|v| v.to_string()),
+                        last_modified: response.header("Last-Modified").map(|v| v.to_string()),
+                        last_status: response.status(),
+                    };
+                    cache.lock().unwrap().insert(url.clone(), entry);
+                }
+
+                break (Ok(response.status()), current_url);
+            }
+            Err(e) => break (Err(format!("Request failed: {} for URL {}", e, current_url)), current_url),
+        }
+    };
 
     //measures the response time
     let response_time = start.elapsed();
@@ -62,22 +579,37 @@ pub fn website_checker(url: String, timeout: Duration) -> WebsiteStatus {
         response_time,
         timestamp,
         headers_valid,
+        changed,
+        poll_interval,
+        redirect_chain,
+        final_url,
+        tail,
+        bytes_added,
+        auth_applied,
     }
 }
 
 //monitor the list of websites concurrenly using multiple workers
 fn monitor_websites(
+    client: Arc<dyn HttpClient + Send + Sync>,
+    cache: Arc<Mutex<Cache>>,
     urls: Vec<String>,
     worker_num: usize,
     timeout: Duration,
     retries: usize,
+    default_interval: Duration,
+    redirect_policy: RedirectPolicy,
+    tail_mode: bool,
+    tail_offsets: Arc<Mutex<TailOffsets>>,
+    user_agent: Arc<String>,
+    auth_config: Arc<AuthConfig>,
 ) -> Vec<WebsiteStatus> {
     //create a channel for sending results from workers
     let (sender, receiver) = mpsc::channel();
-    
+
     //mutex protected queue of URLs
     let urls = Arc::new(Mutex::new(urls.into_iter().collect::<VecDeque<String>>()));
-    
+
     //vector to store worker thread handles
     let mut handles = vec![];
 
@@ -86,6 +618,11 @@ fn monitor_websites(
         //creates clones for each thread and their URL queue
         let sender = sender.clone();
         let urls = Arc::clone(&urls);
+        let client = Arc::clone(&client);
+        let cache = Arc::clone(&cache);
+        let tail_offsets = Arc::clone(&tail_offsets);
+        let user_agent = Arc::clone(&user_agent);
+        let auth_config = Arc::clone(&auth_config);
         let handle = thread::spawn(move || {
             //processes URLs until empty
             while let Some(url) = {
@@ -96,7 +633,7 @@ fn monitor_websites(
 
                 //retry if it fails
                 for _ in 0..=retries {
-                    result = Some(website_checker(url.clone(), timeout));
+                    result = Some(website_checker(client.as_ref(), url.clone(), timeout, &cache, default_interval, redirect_policy, tail_mode, &tail_offsets, &user_agent, &auth_config));
                     if let Some(WebsiteStatus { status: Ok(_), .. }) = result {
                         break;
                     }
@@ -122,6 +659,75 @@ fn monitor_websites(
     receiver.iter().collect()
 }
 
+//continuously re-checks URLs on a schedule derived from each response's own Cache-Control
+//header, instead of hammering every URL on a fixed timer
+fn run_daemon(
+    client: Arc<dyn HttpClient + Send + Sync>,
+    cache: Arc<Mutex<Cache>>,
+    urls: Vec<String>,
+    worker_num: usize,
+    timeout: Duration,
+    retries: usize,
+    default_interval: Duration,
+    redirect_policy: RedirectPolicy,
+    cache_path: &str,
+    tail_mode: bool,
+    tail_offsets: Arc<Mutex<TailOffsets>>,
+    offsets_path: &str,
+    user_agent: Arc<String>,
+    auth_config: Arc<AuthConfig>,
+) -> ! {
+    //min-heap of (next check time, url), ordered soonest-first via Reverse
+    let mut schedule: BinaryHeap<Reverse<(Instant, String)>> = BinaryHeap::new();
+    let now = Instant::now();
+    for url in urls {
+        schedule.push(Reverse((now, url)));
+    }
+
+    loop {
+        //pop every URL whose scheduled time has arrived
+        let mut due = Vec::new();
+        while let Some(&Reverse((when, _))) = schedule.peek() {
+            if when > Instant::now() {
+                break;
+            }
+            let Reverse((_, url)) = schedule.pop().unwrap();
+            due.push(url);
+        }
+
+        if due.is_empty() {
+            thread::sleep(Duration::from_millis(100));
+            continue;
+        }
+
+        let results = monitor_websites(
+            Arc::clone(&client),
+            Arc::clone(&cache),
+            due,
+            worker_num,
+            timeout,
+            retries,
+            default_interval,
+            redirect_policy,
+            tail_mode,
+            Arc::clone(&tail_offsets),
+            Arc::clone(&user_agent),
+            Arc::clone(&auth_config),
+        );
+
+        let now = Instant::now();
+        for status in results {
+            println!("{:?}", status);
+            schedule.push(Reverse((now + status.poll_interval, status.url.clone())));
+        }
+
+        save_cache(cache_path, &cache.lock().unwrap());
+        if tail_mode {
+            save_offsets(offsets_path, &tail_offsets.lock().unwrap());
+        }
+    }
+}
+
 pub fn read_from_file(file_path: &str) -> std::io::Result<Vec<String>> {
     let file = File::open(file_path)?;
     let reader = BufReader::new(file);
@@ -151,6 +757,8 @@ fn summarize_results(results: &[WebsiteStatus]) {
     let total = results.len();
     let successes = results.iter().filter(|r| r.status.is_ok()).count();
     let failures = total - successes;
+    let served_from_cache = results.iter().filter(|r| r.changed == Some(false)).count();
+    let redirected = results.iter().filter(|r| !r.redirect_chain.is_empty()).count();
 
     //calcuates average time
     let avg_response_time = results.iter().map(|r| r.response_time).sum::<Duration>() / (total as u32);
@@ -160,18 +768,55 @@ fn summarize_results(results: &[WebsiteStatus]) {
     println!("Total URLs: {}", total);
     println!("Successful: {}", successes);
     println!("Failed: {}", failures);
+    println!("Served from cache (304 Not Modified): {}", served_from_cache);
+    println!("Redirected: {}", redirected);
     println!("Average Response Time: {:?}", avg_response_time);
+
+    //only meaningful when tail mode is on, so skip the line entirely otherwise
+    let tail_total: u64 = results.iter().filter_map(|r| r.bytes_added).sum();
+    if results.iter().any(|r| r.bytes_added.is_some()) {
+        println!("Bytes added (tail mode): {}", tail_total);
+    }
+
+    //a 401/403 despite sending credentials usually means the configured token expired
+    let auth_rejections: Vec<&WebsiteStatus> = results
+        .iter()
+        .filter(|r| r.auth_applied && matches!(r.status, Ok(401) | Ok(403)))
+        .collect();
+    if !auth_rejections.is_empty() {
+        println!("Auth configured but rejected (401/403):");
+        for r in auth_rejections {
+            println!("  {} -> {:?}", r.url, r.status);
+        }
+    }
 }
 
 fn main() {
     //collects command line arguments
     let args: Vec<String> = env::args().collect();
     let file_path = "urls.txt";
+    let cache_path = "cache.json";
+    let offsets_path = "tail_offsets.json";
 
     //Set up the defaults if not specified
     let worker_num: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(4);
     let timeout: Duration = args.get(2).and_then(|s| s.parse().ok()).map(Duration::from_secs).unwrap_or(Duration::from_secs(5));
     let retries: usize = args.get(3).and_then(|s| s.parse().ok()).unwrap_or(3);
+    let default_interval: Duration = args.get(4).and_then(|s| s.parse().ok()).map(Duration::from_secs).unwrap_or(Duration::from_secs(300));
+    let daemon_mode = args.get(5).map(|s| s == "daemon").unwrap_or(false);
+    let redirect_policy = RedirectPolicy {
+        max_hops: args.get(6).and_then(|s| s.parse().ok()).unwrap_or(RedirectPolicy::default().max_hops),
+    };
+    let timeouts = TimeoutConfig {
+        connect: args.get(7).and_then(|s| s.parse().ok()).map(Duration::from_secs).unwrap_or(TimeoutConfig::default().connect),
+        read: args.get(8).and_then(|s| s.parse().ok()).map(Duration::from_secs).unwrap_or(TimeoutConfig::default().read),
+        total: timeout,
+    };
+    //"tail" mode polls append-only resources with Range requests instead of refetching the whole body
+    let tail_mode = args.get(9).map(|s| s == "tail").unwrap_or(false);
+    //servers often reject requests with no User-Agent, or a generic library default
+    let user_agent = Arc::new(args.get(10).cloned().unwrap_or_else(|| "FinalProjectWebsiteChecker/0.1".to_string()));
+    let auth_config = Arc::new(load_auth_config("auth.json"));
 
     //reads the urls
     match read_from_file(file_path) {
@@ -182,14 +827,34 @@ fn main() {
             }
 
             //moniters websites and collect results
+            let client: Arc<dyn HttpClient + Send + Sync> = Arc::new(UreqClient::new(timeouts));
+            let cache = Arc::new(Mutex::new(load_cache(cache_path)));
+            let tail_offsets = Arc::new(Mutex::new(if tail_mode { load_offsets(offsets_path) } else { TailOffsets::new() }));
+
+            if daemon_mode {
+                //never returns, keeps re-polling on a schedule driven by Cache-Control
+                run_daemon(client, cache, urls, worker_num, timeout, retries, default_interval, redirect_policy, cache_path, tail_mode, tail_offsets, offsets_path, user_agent, auth_config);
+            }
+
             let start = Instant::now();
-            let results = monitor_websites(urls.clone(), worker_num, timeout, retries);
+            let results = monitor_websites(client, Arc::clone(&cache), urls.clone(), worker_num, timeout, retries, default_interval, redirect_policy, tail_mode, Arc::clone(&tail_offsets), Arc::clone(&user_agent), Arc::clone(&auth_config));
             for status in &results {
-                //print the status of each websit
+                //print the status of each website, including any redirect chain it followed
                 println!("{:?}", status);
+                if !status.redirect_chain.is_empty() {
+                    print!("  redirects:");
+                    for (hop_url, hop_status) in &status.redirect_chain {
+                        print!(" {} ({}) ->", hop_url, hop_status);
+                    }
+                    println!(" {}", status.final_url);
+                }
             }
 
             summarize_results(&results);
+            save_cache(cache_path, &cache.lock().unwrap());
+            if tail_mode {
+                save_offsets(offsets_path, &tail_offsets.lock().unwrap());
+            }
 
             println!(
                 "Total execution time: {:?}",
@@ -202,40 +867,148 @@ fn main() {
     }
 }
 
-//gets used in the testing portion
-fn start_mock_server(address: &str, response_code: u16, response_body: &str) {
-    
-    //binds the server to address
-    let listener = TcpListener::bind(address).unwrap();
-    println!("Mock server running on {}", address);
-
-    for stream in listener.incoming() {
-        match stream {
-            Ok(mut stream) => {
-                let response = format!(
-                    //Creates a mock HTTP response
-                    "HTTP/1.1 {} OK\r\nContent-Length: {}\r\n\r\n{}",
-                    response_code,
-                    response_body.len(),
-                    response_body
-                );
-                //Sends the response to the client and then flushes the stream
-                stream.write_all(response.as_bytes()).unwrap();
-                stream.flush().unwrap();
-            }
-            Err(e) => {
-                eprintln!("Connection failed: {}", e);
-            }
+//a single registered (method, path) -> response rule
+struct MockRoute {
+    method: String,
+    path: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+//maps the status codes this project actually exercises to their reason phrase
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        206 => "Partial Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        416 => "Range Not Satisfiable",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+fn render_response(status: u16, headers: &[(String, String)], body: &str) -> String {
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: {}\r\n",
+        status,
+        reason_phrase(status),
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    response
+}
+
+//reads just the request line (method + path) off the socket; the test fixtures never need
+//to inspect request headers or bodies
+fn read_request_line(stream: &TcpStream) -> (String, String) {
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).ok();
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or("GET").to_string();
+    let path = parts.next().unwrap_or("/").to_string();
+    (method, path)
+}
+
+//a small mockito-style mock server used by the test suite: register rules with
+//`.mock(method, path).with_status(..).with_header(..).with_body(..)`, a "*" path matches
+//any request path, and anything that matches no rule gets a 404
+struct MockServer {
+    routes: Vec<MockRoute>,
+}
+
+impl MockServer {
+    fn new() -> Self {
+        MockServer { routes: Vec::new() }
+    }
+
+    //registers a new route, defaulting to a bare 200 until with_status/with_header/with_body refine it
+    fn mock(mut self, method: &str, path: &str) -> Self {
+        self.routes.push(MockRoute {
+            method: method.to_string(),
+            path: path.to_string(),
+            status: 200,
+            headers: Vec::new(),
+            body: String::new(),
+        });
+        self
+    }
+
+    //the with_* methods configure the most recently added route
+    fn with_status(mut self, status: u16) -> Self {
+        if let Some(route) = self.routes.last_mut() {
+            route.status = status;
         }
+        self
+    }
+
+    fn with_header(mut self, name: &str, value: &str) -> Self {
+        if let Some(route) = self.routes.last_mut() {
+            route.headers.push((name.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    fn with_body(mut self, body: &str) -> Self {
+        if let Some(route) = self.routes.last_mut() {
+            route.body = body.to_string();
+        }
+        self
+    }
+
+    //binds `address` and serves registered routes on a background thread until the process exits
+    fn start(self, address: &str) {
+        let listener = TcpListener::bind(address).unwrap();
+        println!("Mock server running on {}", address);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(mut stream) => {
+                        let (method, path) = read_request_line(&stream);
+                        let matched = self.routes.iter().find(|route| {
+                            route.method.eq_ignore_ascii_case(&method)
+                                && (route.path == "*" || route.path == path)
+                        });
+
+                        let response = match matched {
+                            Some(route) => render_response(route.status, &route.headers, &route.body),
+                            None => render_response(404, &[], ""),
+                        };
+
+                        stream.write_all(response.as_bytes()).unwrap();
+                        stream.flush().unwrap();
+                    }
+                    Err(e) => {
+                        eprintln!("Connection failed: {}", e);
+                    }
+                }
+            }
+        });
     }
 }
 
-//starts a mock server in a separate thread
-fn mock_server_thread() {
-    thread::spawn(move || {
-        //start mock with 200 ok response
-        start_mock_server("127.0.0.1:8080", 200, "Mock Response");
-    });
+//starts a fixture on `127.0.0.1:<port>` where any GET gets a 200. callers must each use their
+//own port - a shared hardcoded port races when more than one test binds it in the same process
+fn mock_server_thread(port: u16) {
+    MockServer::new()
+        .mock("GET", "*")
+        .with_status(200)
+        .with_body("Mock Response")
+        .start(&format!("127.0.0.1:{}", port));
 }
 
 #[cfg(test)]
@@ -243,55 +1016,511 @@ mod tests {
     use super::*;
     use std::time::Duration;
     use std::time::Instant;
-    
+
+    fn empty_cache() -> Arc<Mutex<Cache>> {
+        Arc::new(Mutex::new(Cache::new()))
+    }
+
+    fn empty_offsets() -> Arc<Mutex<TailOffsets>> {
+        Arc::new(Mutex::new(TailOffsets::new()))
+    }
+
+    fn empty_auth() -> AuthConfig {
+        AuthConfig::new()
+    }
+
+    const TEST_USER_AGENT: &str = "FinalProjectWebsiteChecker/0.1";
+
+    #[test]
+    fn test_parse_poll_interval_max_age() {
+        let interval = parse_poll_interval(Some("max-age=120"), Duration::from_secs(300));
+        assert_eq!(interval, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_parse_poll_interval_no_store_means_refetch_now() {
+        let interval = parse_poll_interval(Some("no-store"), Duration::from_secs(300));
+        assert_eq!(interval, Duration::from_secs(0));
+    }
+
+    #[test]
+    fn test_parse_poll_interval_falls_back_to_default() {
+        let interval = parse_poll_interval(None, Duration::from_secs(300));
+        assert_eq!(interval, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_timeout_phase_classifies_connect_and_read() {
+        assert_eq!(timeout_phase("connect timed out"), Some("connect"));
+        assert_eq!(timeout_phase("read timed out after 10s"), Some("read"));
+        assert_eq!(timeout_phase("timed out"), Some("total"));
+    }
+
+    #[test]
+    fn test_timeout_phase_ignores_non_timeout_errors() {
+        assert_eq!(timeout_phase("connection refused"), None);
+    }
+
     #[test]
     fn test_validate_headers_invalid() {
-        // creates a valid response with status code 200 and appropriate headers
-        let mock_response = ureq::Response::new(200, "OK", "body content");
-    
-        // validate the headers
-        if let Ok(response) = mock_response {
-            // Check that the expected header exists
-            // or whatever header is default
-            assert_eq!(response.header("Content-Type"), None);
-    
-            // modify the validation condition for test purposes
-            assert!(!validate_headers(&response));
-        } else {
-            panic!("Failed to create mock response");
-        }
-    }    
+        // a response with no headers at all should not validate
+        let mock_response = HttpResp {
+            status: 200,
+            headers: vec![],
+            body: "body content".to_string(),
+        };
+
+        assert_eq!(mock_response.header("Content-Type"), None);
+        assert!(!validate_headers(&mock_response));
+    }
+
+    #[test]
+    fn test_validate_headers_valid() {
+        // a response with a JSON content type should validate
+        let mock_response = HttpResp {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: "{}".to_string(),
+        };
+
+        assert!(validate_headers(&mock_response));
+    }
 
     #[test]
     fn test_website_checker_success() {
-        // mock website status with fake response time and status
-        let result = website_checker("http://example.com".to_string(), Duration::from_secs(5));
+        // deterministic success case using a mocked client instead of a real socket
+        let client = MockClient::new().with_response(
+            "http://example.com",
+            HttpResp {
+                status: 200,
+                headers: vec![],
+                body: String::new(),
+            },
+        );
+
+        let result = website_checker(&client, "http://example.com".to_string(), Duration::from_secs(5), &empty_cache(), Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth());
 
         assert_eq!(result.url, "http://example.com");
-        assert!(result.status.is_ok());
-        assert!(result.response_time > Duration::from_secs(0));
+        assert_eq!(result.status, Ok(200));
         assert_eq!(result.headers_valid, false);
+        assert_eq!(result.changed, None); // no prior cache entry on the first check
     }
 
     #[test]
     fn test_website_checker_failure() {
-        let result = website_checker("http://nonexistent-url.com".to_string(), Duration::from_secs(5));
+        // deterministic failure case using a mocked client instead of a real socket
+        let client = MockClient::new().with_error("http://nonexistent-url.com", "connection refused");
+
+        let result = website_checker(&client, "http://nonexistent-url.com".to_string(), Duration::from_secs(5), &empty_cache(), Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth());
 
         assert_eq!(result.url, "http://nonexistent-url.com");
         assert!(result.status.is_err());
     }
 
+    #[test]
+    fn test_website_checker_not_modified_reuses_cached_status() {
+        // first check populates the cache with an ETag
+        let client = MockClient::new().with_response(
+            "http://example.com",
+            HttpResp {
+                status: 200,
+                headers: vec![("ETag".to_string(), "\"abc123\"".to_string())],
+                body: String::new(),
+            },
+        );
+        let cache = empty_cache();
+        let first = website_checker(&client, "http://example.com".to_string(), Duration::from_secs(5), &cache, Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth());
+        assert_eq!(first.changed, None);
+        assert_eq!(first.status, Ok(200));
+
+        // second check gets a 304, should be reported as unchanged and reuse the 200 status
+        let client = MockClient::new().with_response(
+            "http://example.com",
+            HttpResp {
+                status: 304,
+                headers: vec![],
+                body: String::new(),
+            },
+        );
+        let second = website_checker(&client, "http://example.com".to_string(), Duration::from_secs(5), &cache, Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth());
+        assert_eq!(second.changed, Some(false));
+        assert_eq!(second.status, Ok(200));
+    }
+
+    #[test]
+    fn test_resolve_location_absolute_and_relative() {
+        assert_eq!(
+            resolve_location("http://example.com/a/b", "http://other.com/c"),
+            "http://other.com/c"
+        );
+        assert_eq!(
+            resolve_location("http://example.com/a/b", "/c"),
+            "http://example.com/c"
+        );
+        assert_eq!(
+            resolve_location("http://example.com/a/b", "c"),
+            "http://example.com/a/c"
+        );
+    }
+
+    #[test]
+    fn test_website_checker_follows_redirect_chain() {
+        let client = MockClient::new()
+            .with_response(
+                "http://example.com/old",
+                HttpResp {
+                    status: 301,
+                    headers: vec![("Location".to_string(), "/new".to_string())],
+                    body: String::new(),
+                },
+            )
+            .with_response(
+                "http://example.com/new",
+                HttpResp {
+                    status: 200,
+                    headers: vec![],
+                    body: String::new(),
+                },
+            );
+
+        let result = website_checker(
+            &client,
+            "http://example.com/old".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            false,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(200));
+        assert_eq!(result.final_url, "http://example.com/new");
+        assert_eq!(result.redirect_chain, vec![("http://example.com/old".to_string(), 301)]);
+    }
+
+    #[test]
+    fn test_website_checker_forwards_conditional_headers_past_redirect() {
+        // the cached ETag belongs to "http://example.com/old", but the resource permanently
+        // redirects to "/new" - the conditional header needs to reach the final hop, not just
+        // get dropped after the first request
+        let cache = empty_cache();
+        cache.lock().unwrap().insert(
+            "http://example.com/old".to_string(),
+            CacheEntry {
+                etag: Some("\"abc123\"".to_string()),
+                last_modified: None,
+                last_status: 200,
+            },
+        );
+
+        let client = MockClient::new()
+            .with_response(
+                "http://example.com/old",
+                HttpResp {
+                    status: 301,
+                    headers: vec![("Location".to_string(), "/new".to_string())],
+                    body: String::new(),
+                },
+            )
+            .with_response(
+                "http://example.com/new",
+                HttpResp {
+                    status: 304,
+                    headers: vec![],
+                    body: String::new(),
+                },
+            );
+
+        let result = website_checker(
+            &client,
+            "http://example.com/old".to_string(),
+            Duration::from_secs(5),
+            &cache,
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            false,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(200));
+        assert_eq!(result.changed, Some(false));
+        let headers = client.headers_sent_to("http://example.com/new").unwrap();
+        assert!(headers.contains(&("If-None-Match".to_string(), "\"abc123\"".to_string())));
+    }
+
+    #[test]
+    fn test_website_checker_detects_redirect_loop() {
+        let client = MockClient::new()
+            .with_response(
+                "http://example.com/a",
+                HttpResp {
+                    status: 302,
+                    headers: vec![("Location".to_string(), "/b".to_string())],
+                    body: String::new(),
+                },
+            )
+            .with_response(
+                "http://example.com/b",
+                HttpResp {
+                    status: 302,
+                    headers: vec![("Location".to_string(), "/a".to_string())],
+                    body: String::new(),
+                },
+            );
+
+        let result = website_checker(
+            &client,
+            "http://example.com/a".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            false,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert!(result.status.is_err());
+    }
+
+    #[test]
+    fn test_website_checker_tail_mode_tracks_offset_on_206() {
+        let client = MockClient::new().with_response(
+            "http://example.com/log",
+            HttpResp {
+                status: 206,
+                headers: vec![("Content-Range".to_string(), "bytes 100-149/150".to_string())],
+                body: "line1\nline2".to_string(),
+            },
+        );
+
+        let offsets = empty_offsets();
+        let result = website_checker(
+            &client,
+            "http://example.com/log".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            true,
+            &offsets,
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(206));
+        assert_eq!(result.bytes_added, Some(11));
+        assert_eq!(result.tail.as_deref(), Some("line1\nline2"));
+        assert_eq!(*offsets.lock().unwrap().get("http://example.com/log").unwrap(), 150);
+    }
+
+    #[test]
+    fn test_website_checker_tail_mode_416_means_no_new_data() {
+        let client = MockClient::new().with_response(
+            "http://example.com/log",
+            HttpResp {
+                status: 416,
+                headers: vec![],
+                body: String::new(),
+            },
+        );
+
+        let result = website_checker(
+            &client,
+            "http://example.com/log".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            true,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(416));
+        assert_eq!(result.bytes_added, Some(0));
+        assert_eq!(result.tail, None);
+    }
+
+    #[test]
+    fn test_website_checker_tail_mode_error_response_does_not_clobber_offset_or_cache() {
+        // a transient 500 is not "the whole body", it shouldn't overwrite the real tracked
+        // offset or cache entry with the error page's length/ETag
+        let cache = empty_cache();
+        cache.lock().unwrap().insert(
+            "http://example.com/log".to_string(),
+            CacheEntry {
+                etag: Some("\"real-etag\"".to_string()),
+                last_modified: None,
+                last_status: 206,
+            },
+        );
+        let offsets = empty_offsets();
+        offsets.lock().unwrap().insert("http://example.com/log".to_string(), 150);
+
+        let client = MockClient::new().with_response(
+            "http://example.com/log",
+            HttpResp {
+                status: 500,
+                headers: vec![("ETag".to_string(), "\"error-page-etag\"".to_string())],
+                body: "internal server error".to_string(),
+            },
+        );
+
+        let result = website_checker(
+            &client,
+            "http://example.com/log".to_string(),
+            Duration::from_secs(5),
+            &cache,
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            true,
+            &offsets,
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(500));
+        assert_eq!(*offsets.lock().unwrap().get("http://example.com/log").unwrap(), 150);
+        assert_eq!(
+            cache.lock().unwrap().get("http://example.com/log").unwrap().etag.as_deref(),
+            Some("\"real-etag\"")
+        );
+    }
+
+    #[test]
+    fn test_host_matches_exact_and_wildcard() {
+        assert!(host_matches("example.com", "example.com"));
+        assert!(!host_matches("example.com", "api.example.com"));
+        assert!(host_matches("*.example.com", "api.example.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+    }
+
+    #[test]
+    fn test_website_checker_applies_matching_auth_header() {
+        let mut auth = AuthConfig::new();
+        auth.insert(
+            "example.com".to_string(),
+            AuthEntry { scheme: "Bearer".to_string(), token: "secret".to_string() },
+        );
+
+        let client = MockClient::new()
+            .with_response("http://example.com", HttpResp { status: 200, headers: vec![], body: String::new() });
+
+        let result = website_checker(&client, "http://example.com".to_string(), Duration::from_secs(5), &empty_cache(), Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &auth);
+
+        assert!(result.auth_applied);
+    }
+
+    #[test]
+    fn test_website_checker_no_auth_applied_without_matching_host() {
+        let client = MockClient::new()
+            .with_response("http://example.com", HttpResp { status: 200, headers: vec![], body: String::new() });
+
+        let result = website_checker(&client, "http://example.com".to_string(), Duration::from_secs(5), &empty_cache(), Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth());
+
+        assert!(!result.auth_applied);
+    }
+
+    #[test]
+    fn test_mock_server_matches_path_and_status() {
+        MockServer::new()
+            .mock("GET", "/widgets")
+            .with_status(201)
+            .with_header("Content-Type", "application/json")
+            .with_body("{\"id\":1}")
+            .start("127.0.0.1:8081");
+
+        let client = UreqClient::new(TimeoutConfig::default());
+        let result = website_checker(
+            &client,
+            "http://127.0.0.1:8081/widgets".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            false,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(201));
+        assert!(result.headers_valid); // Content-Type: application/json
+    }
+
+    #[test]
+    fn test_mock_server_unmatched_path_is_404() {
+        MockServer::new()
+            .mock("GET", "/widgets")
+            .with_status(200)
+            .start("127.0.0.1:8082");
+
+        let client = UreqClient::new(TimeoutConfig::default());
+        let result = website_checker(
+            &client,
+            "http://127.0.0.1:8082/nothing-here".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            false,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(404));
+    }
+
+    #[test]
+    fn test_mock_server_redirect_chain_over_real_socket() {
+        MockServer::new()
+            .mock("GET", "/old")
+            .with_status(301)
+            .with_header("Location", "/new")
+            .mock("GET", "/new")
+            .with_status(200)
+            .start("127.0.0.1:8083");
+
+        let client = UreqClient::new(TimeoutConfig::default());
+        let result = website_checker(
+            &client,
+            "http://127.0.0.1:8083/old".to_string(),
+            Duration::from_secs(5),
+            &empty_cache(),
+            Duration::from_secs(300),
+            RedirectPolicy::default(),
+            false,
+            &empty_offsets(),
+            TEST_USER_AGENT,
+            &empty_auth(),
+        );
+
+        assert_eq!(result.status, Ok(200));
+        assert_eq!(result.final_url, "http://127.0.0.1:8083/new");
+        assert_eq!(result.redirect_chain, vec![("http://127.0.0.1:8083/old".to_string(), 301)]);
+    }
+
     #[test]
     fn test_integration_with_mock_server() {
-        // start mock server in a separate thread
-        mock_server_thread();
+        // start mock server in a separate thread, on a port not used by any other test
+        mock_server_thread(8084);
 
         // simulate checking a website with the mock server
+        let client = UreqClient::new(TimeoutConfig::default());
         let start = Instant::now();
-        let result = website_checker("http://127.0.0.1:8080".to_string(), Duration::from_secs(5));
-        
+        let result = website_checker(&client, "http://127.0.0.1:8084".to_string(), Duration::from_secs(5), &empty_cache(), Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth());
+
         assert!(result.status.is_ok());
-        assert_eq!(result.url, "http://127.0.0.1:8080");
+        assert_eq!(result.url, "http://127.0.0.1:8084");
         assert!(result.response_time < Duration::from_secs(5));
 
         // Verify it finished within reasonable time
@@ -301,19 +1530,20 @@ mod tests {
     #[test]
     fn test_performance_multiple_concurrent_requests() {
         let urls = vec![
-            "http://127.0.0.1:8080".to_string(),
-            "http://127.0.0.1:8080".to_string(),
-            "http://127.0.0.1:8080".to_string(),
+            "http://127.0.0.1:8085".to_string(),
+            "http://127.0.0.1:8085".to_string(),
+            "http://127.0.0.1:8085".to_string(),
         ];
         let worker_num = 4;
         let timeout = Duration::from_secs(5);
         let retries = 3;
 
-        // start mock server in a separate thread
-        mock_server_thread();
+        // start mock server in a separate thread, on a port not used by any other test
+        mock_server_thread(8085);
 
+        let client: Arc<dyn HttpClient + Send + Sync> = Arc::new(UreqClient::new(TimeoutConfig::default()));
         let start = Instant::now();
-        let results = monitor_websites(urls, worker_num, timeout, retries);
+        let results = monitor_websites(client, empty_cache(), urls, worker_num, timeout, retries, Duration::from_secs(300), RedirectPolicy::default(), false, empty_offsets(), Arc::new(TEST_USER_AGENT.to_string()), Arc::new(empty_auth()));
 
          // ensures tests complete in time
         assert!(results.len() > 0);
@@ -322,15 +1552,18 @@ mod tests {
 
     #[test]
     fn test_retry_on_failure() {
-        let url = "http://127.0.0.1:8080".to_string();
+        let url = "http://127.0.0.1:8086".to_string();
         let timeout = Duration::from_secs(2);
         let retries = 3;
 
-        mock_server_thread();
+        // start mock server in a separate thread, on a port not used by any other test
+        mock_server_thread(8086);
 
+        let client = UreqClient::new(TimeoutConfig::default());
+        let cache = empty_cache();
         let mut result = None;
         for _ in 0..retries {
-            result = Some(website_checker(url.clone(), timeout));
+            result = Some(website_checker(&client, url.clone(), timeout, &cache, Duration::from_secs(300), RedirectPolicy::default(), false, &empty_offsets(), TEST_USER_AGENT, &empty_auth()));
             if result.as_ref().unwrap().status.is_ok() {
                 break;
             }
@@ -340,4 +1573,3 @@ mod tests {
         assert!(result.unwrap().status.is_ok());
     }
 }
-